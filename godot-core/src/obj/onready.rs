@@ -6,6 +6,7 @@
  */
 
 use crate::property::{Export, Property, PropertyHintInfo};
+use std::cell::UnsafeCell;
 use std::mem;
 
 /// Ergonomic late-initialization container with `ready()` support.
@@ -14,17 +15,23 @@ use std::mem;
 /// Godot in particular encourages initialization inside `ready()`, e.g. to access the scene tree after a node is inserted into it.
 /// The alternative to using this pattern is [`Option<T>`][option], which needs to be explicitly unwrapped with `unwrap()` or `expect()` each time.
 ///
-/// `OnReady<T>` should always be used as a field. There are two modes to use it:
+/// `OnReady<T>` should always be used as a field. There are three modes to use it:
 ///
 /// 1. **Automatic mode, using [`new()`](Self::new).**<br>
 ///    Before `ready()` is called, all `OnReady` fields constructed with `new()` are automatically initialized, in the order of
 ///    declaration. This means that you can safely access them in `ready()`.<br><br>
 /// 2. **Manual mode, using [`manual()`](Self::manual).**<br>
 ///    These fields are left uninitialized until you call [`init()`][Self::init] on them. This is useful if you need more complex
-///    initialization scenarios than a closure allows. If you forget initialization, a panic will occur on first access.
+///    initialization scenarios than a closure allows. If you forget initialization, a panic will occur on first access.<br><br>
+/// 3. **Lazy mode, using [`lazy()`](Self::lazy).**<br>
+///    The closure is not run until the field is first accessed through [`Deref`][std::ops::Deref] or [`DerefMut`][std::ops::DerefMut].
+///    This is useful for expensive initialization that might not be needed at all, or that depends on state which is only available
+///    some time after `ready()`.
 ///
 /// Conceptually, `OnReady<T>` is very close to [once_cell's `Lazy<T>`][lazy], with additional hooks into the Godot lifecycle.
-/// The absence of methods to check initialization state is deliberate: you don't need them if you follow the above two patterns.
+/// Regular access happens through [`Deref`][std::ops::Deref]/[`DerefMut`][std::ops::DerefMut] and panics if the value isn't ready yet;
+/// for code that must probe without risking a panic (editor tooling, `#[export]` fields touched before `ready()`, diagnostics),
+/// use the non-panicking [`get()`][Self::get], [`get_mut()`][Self::get_mut] or [`is_init()`][Self::is_init] instead.
 /// This container is not designed as a general late-initialization solution, but tailored to the `ready()` semantics of Godot.
 ///
 /// This type is not thread-safe. `ready()` runs on the main thread and you are expected to access its value on the main thread, as well.
@@ -62,7 +69,8 @@ use std::mem;
 ///     }
 /// }
 pub struct OnReady<T> {
-    state: InitState<T>,
+    // UnsafeCell is needed because lazy fields must be initialized from `deref()`, which only gets `&self`.
+    state: UnsafeCell<InitState<T>>,
 }
 
 impl<T> OnReady<T> {
@@ -80,9 +88,9 @@ impl<T> OnReady<T> {
         F: FnOnce() -> T + 'static,
     {
         Self {
-            state: InitState::AutoPrepared {
+            state: UnsafeCell::new(InitState::AutoPrepared {
                 initializer: Box::new(init_fn),
-            },
+            }),
         }
     }
 
@@ -91,7 +99,42 @@ impl<T> OnReady<T> {
     /// If you use this method, you _must_ call [`init()`][Self::init] during the `ready()` callback, otherwise a panic will occur.
     pub fn manual() -> Self {
         Self {
-            state: InitState::ManualUninitialized,
+            state: UnsafeCell::new(InitState::ManualUninitialized),
+        }
+    }
+
+    /// Defer initialization until the value is first accessed.
+    ///
+    /// Unlike [`new()`][Self::new], the closure is not run before `ready()`; instead, it runs the first time the field is
+    /// dereferenced (through [`Deref`][std::ops::Deref] or [`DerefMut`][std::ops::DerefMut]), wherever that happens to be.
+    /// This mirrors the semantics of [`LazyCell`][std::cell::LazyCell] / once_cell's `Lazy<T>`, and is useful for expensive
+    /// initialization that might not be needed at all, or that depends on state only available some time after `ready()`.
+    pub fn lazy<F>(init_fn: F) -> Self
+    where
+        F: FnOnce() -> T + 'static,
+    {
+        Self {
+            state: UnsafeCell::new(InitState::LazyPrepared {
+                initializer: Box::new(init_fn),
+            }),
+        }
+    }
+
+    /// Sets the value if not yet initialized, without panicking.
+    ///
+    /// Returns `Ok(())` if the container was [`manual()`][Self::manual] and still uninitialized, transitioning it to initialized.
+    /// Otherwise, returns `Err(value)`, handing the value back to the caller -- this includes the case where the container was
+    /// constructed with [`new()`][Self::new] or [`lazy()`][Self::lazy], or was already initialized before.
+    ///
+    /// This is the fallible counterpart to [`init()`][Self::init], for flows where the caller isn't statically sure whether
+    /// the field has already been set (e.g. optional re-wiring after a scene reload).
+    pub fn set(&mut self, value: T) -> Result<(), T> {
+        match self.state.get_mut() {
+            InitState::ManualUninitialized { .. } => {
+                *self.state.get_mut() = InitState::Initialized { value };
+                Ok(())
+            }
+            _ => Err(value),
         }
     }
 
@@ -99,19 +142,25 @@ impl<T> OnReady<T> {
     ///
     /// # Panics
     /// - If `init()` was called before.
-    /// - If this object was already provided with a closure during construction, in [`Self::new()`].
+    /// - If this object was already provided with a closure during construction, in [`Self::new()`] or [`Self::lazy()`].
     pub fn init(&mut self, value: T) {
-        match &self.state {
-            InitState::ManualUninitialized { .. } => {
-                self.state = InitState::Initialized { value };
-            }
+        if self.set(value).is_ok() {
+            return;
+        }
+
+        match self.state.get_mut() {
+            InitState::ManualUninitialized { .. } => unreachable!(), // set() above would have succeeded
             InitState::AutoPrepared { .. } => {
                 panic!("cannot call init() on auto-initialized OnReady objects")
             }
+            InitState::LazyPrepared { .. } => {
+                panic!("cannot call init() on lazily-initialized OnReady objects")
+            }
             InitState::AutoInitializing => {
                 // SAFETY: Loading is ephemeral state that is only set in init_auto() and immediately overwritten.
                 unsafe { std::hint::unreachable_unchecked() }
             }
+            InitState::Poisoned => panic!("OnReady value poisoned: initializer panicked"),
             InitState::Initialized { .. } => {
                 panic!("already initialized; did you call init() more than once?")
             }
@@ -123,28 +172,116 @@ impl<T> OnReady<T> {
     /// # Panics
     /// If the value is already initialized.
     pub(crate) fn init_auto(&mut self) {
-        // Two branches needed, because mem::replace() could accidentally overwrite an already initialized value.
-        match &self.state {
+        // Several branches needed, because mem::replace() could accidentally overwrite an already initialized value.
+        match self.state.get_mut() {
             InitState::ManualUninitialized => return, // skipped
+            InitState::LazyPrepared { .. } => return, // skipped; runs lazily on first access instead
             InitState::AutoPrepared { .. } => {}      // handled below
             InitState::AutoInitializing => {
                 // SAFETY: Loading is ephemeral state that is only set below and immediately overwritten.
                 unsafe { std::hint::unreachable_unchecked() }
             }
+            InitState::Poisoned => panic!("OnReady value poisoned: initializer panicked"),
             InitState::Initialized { .. } => panic!("OnReady object already initialized"),
         };
 
         // Temporarily replace with dummy state, as it's not possible to take ownership of the initializer closure otherwise.
         let InitState::AutoPrepared { initializer } =
-            mem::replace(&mut self.state, InitState::AutoInitializing)
+            mem::replace(self.state.get_mut(), InitState::AutoInitializing)
         else {
             // SAFETY: condition checked above.
             unsafe { std::hint::unreachable_unchecked() }
         };
 
-        self.state = InitState::Initialized {
-            value: initializer(),
+        Self::run_initializer(self.state.get_mut(), initializer);
+    }
+
+    /// Returns a shared reference to the value, or `None` if it isn't initialized yet.
+    ///
+    /// Unlike [`Deref`][std::ops::Deref], this never panics. It also never runs a pending [`lazy()`][Self::lazy] initializer;
+    /// a lazy field that hasn't been accessed yet is reported as uninitialized.
+    pub fn get(&self) -> Option<&T> {
+        // SAFETY: we only take a shared reference here, and don't run any initializer that could mutate `state`.
+        match unsafe { &*self.state.get() } {
+            InitState::Initialized { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns an exclusive reference to the value, or `None` if it isn't initialized yet.
+    ///
+    /// Unlike [`DerefMut`][std::ops::DerefMut], this never panics. It also never runs a pending [`lazy()`][Self::lazy]
+    /// initializer; a lazy field that hasn't been accessed yet is reported as uninitialized.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        match self.state.get_mut() {
+            InitState::Initialized { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the value is currently initialized.
+    ///
+    /// A [`lazy()`][Self::lazy] field that hasn't been accessed yet is reported as uninitialized.
+    pub fn is_init(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Consumes the container and returns the value, if it was initialized.
+    ///
+    /// Returns `None` for any other state, including a not-yet-run [`lazy()`][Self::lazy] initializer, which is not run here.
+    pub fn into_inner(self) -> Option<T> {
+        match self.state.into_inner() {
+            InitState::Initialized { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Takes the value out of the container, if it was initialized, resetting it to manual-uninitialized.
+    ///
+    /// After this call, the container behaves like a fresh [`manual()`][Self::manual] one: it must be re-initialized with
+    /// [`init()`][Self::init] or [`set()`][Self::set] before being accessed again. Returns `None`, and leaves the container
+    /// untouched, if it wasn't initialized.
+    pub fn take(&mut self) -> Option<T> {
+        match mem::replace(self.state.get_mut(), InitState::ManualUninitialized) {
+            InitState::Initialized { value } => Some(value),
+            other => {
+                *self.state.get_mut() = other;
+                None
+            }
+        }
+    }
+
+    /// If this container holds a not-yet-run lazy initializer, runs it now. No-op in all other states.
+    fn ensure_lazy_initialized(&self) {
+        // SAFETY: shared read only; any reference previously handed out by get()/deref() is also shared.
+        if !matches!(unsafe { &*self.state.get() }, InitState::LazyPrepared { .. }) {
+            return;
+        }
+
+        // SAFETY: OnReady is !Sync, so this is the only thread that can ever touch `state`. The `&mut` is only
+        // created while the state is `LazyPrepared`; get() and deref() hand out references only to an
+        // `Initialized` value, so none of them can be alive at this point.
+        let state = unsafe { &mut *self.state.get() };
+
+        // Temporarily replace with dummy state, as it's not possible to take ownership of the initializer closure otherwise.
+        let InitState::LazyPrepared { initializer } = mem::replace(state, InitState::AutoInitializing)
+        else {
+            // SAFETY: condition checked above.
+            unsafe { std::hint::unreachable_unchecked() }
         };
+
+        Self::run_initializer(state, initializer);
+    }
+
+    /// Runs `initializer` and writes its result into `*state` as `Initialized`.
+    ///
+    /// If `initializer` panics, `*state` is left `Poisoned` (via an RAII guard) instead of whatever ephemeral
+    /// placeholder state the caller put there before handing over the closure.
+    fn run_initializer(state: &mut InitState<T>, initializer: Box<dyn FnOnce() -> T>) {
+        let guard = PoisonOnPanic::new(state);
+        let value = initializer();
+        *guard.state = InitState::Initialized { value };
+        guard.defuse();
     }
 }
 
@@ -158,14 +295,20 @@ impl<T> std::ops::Deref for OnReady<T> {
     /// # Panics
     /// If the value is not yet initialized.
     fn deref(&self) -> &Self::Target {
-        match &self.state {
+        self.ensure_lazy_initialized();
+
+        // SAFETY: the only mutation possible through a shared reference is the one performed by
+        // ensure_lazy_initialized() above, which has already run to completion by this point.
+        match unsafe { &*self.state.get() } {
             InitState::ManualUninitialized => {
                 panic!("OnReady manual value uninitialized, did you call init()?")
             }
             InitState::AutoPrepared { .. } => {
                 panic!("OnReady automatic value uninitialized, is only available in ready()")
             }
+            InitState::LazyPrepared { .. } => unreachable!(), // handled by ensure_lazy_initialized()
             InitState::AutoInitializing => unreachable!(),
+            InitState::Poisoned => panic!("OnReady value poisoned: initializer panicked"),
             InitState::Initialized { value } => value,
         }
     }
@@ -177,12 +320,16 @@ impl<T> std::ops::DerefMut for OnReady<T> {
     /// # Panics
     /// If the value is not yet initialized.
     fn deref_mut(&mut self) -> &mut Self::Target {
-        match &mut self.state {
+        self.ensure_lazy_initialized();
+
+        match self.state.get_mut() {
             InitState::Initialized { value } => value,
             InitState::ManualUninitialized { .. } | InitState::AutoPrepared { .. } => {
                 panic!("value not yet initialized")
             }
+            InitState::LazyPrepared { .. } => unreachable!(), // handled by ensure_lazy_initialized()
             InitState::AutoInitializing => unreachable!(),
+            InitState::Poisoned => panic!("OnReady value poisoned: initializer panicked"),
         }
     }
 }
@@ -217,6 +364,198 @@ impl<T: Export> Export for OnReady<T> {
 enum InitState<T> {
     ManualUninitialized,
     AutoPrepared { initializer: Box<dyn FnOnce() -> T> },
+    LazyPrepared { initializer: Box<dyn FnOnce() -> T> },
     AutoInitializing, // needed because state cannot be empty
+    Poisoned,         // the initializer (AutoPrepared/LazyPrepared closure) panicked
     Initialized { value: T },
 }
+
+/// RAII guard that poisons `state` if dropped during an unwind, i.e. if the initializer it guards panics.
+/// [`Self::defuse()`] must be called after writing the final value, to skip this on the normal return path.
+struct PoisonOnPanic<'a, T> {
+    state: &'a mut InitState<T>,
+}
+
+impl<'a, T> PoisonOnPanic<'a, T> {
+    fn new(state: &'a mut InitState<T>) -> Self {
+        Self { state }
+    }
+
+    fn defuse(self) {
+        mem::forget(self);
+    }
+}
+
+impl<T> Drop for PoisonOnPanic<'_, T> {
+    fn drop(&mut self) {
+        *self.state = InitState::Poisoned;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    fn poison_message(result: std::thread::Result<()>) -> &'static str {
+        result
+            .unwrap_err()
+            .downcast_ref::<&str>()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn auto_poisons_on_panic_and_stays_poisoned() {
+        let mut field: OnReady<i32> = OnReady::new(|| panic!("boom"));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| field.init_auto()));
+        assert!(result.is_err());
+
+        // Further access must report the poison, not panic with an internal "unreachable" or hang.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _ = *field;
+        }));
+        assert_eq!(
+            poison_message(result),
+            "OnReady value poisoned: initializer panicked"
+        );
+    }
+
+    #[test]
+    fn lazy_poisons_on_panic_and_stays_poisoned() {
+        let mut field: OnReady<i32> = OnReady::lazy(|| panic!("boom"));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _ = *field;
+        }));
+        assert!(result.is_err());
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| field.init(1)));
+        assert_eq!(
+            poison_message(result),
+            "OnReady value poisoned: initializer panicked"
+        );
+    }
+
+    #[test]
+    fn lazy_runs_once_on_first_access() {
+        let calls = Rc::new(Cell::new(0));
+        let calls_in_closure = Rc::clone(&calls);
+        let mut field = OnReady::lazy(move || {
+            calls_in_closure.set(calls_in_closure.get() + 1);
+            42
+        });
+
+        assert_eq!(calls.get(), 0, "closure must not run before first access");
+
+        assert_eq!(*field, 42);
+        assert_eq!(calls.get(), 1);
+
+        *field += 1;
+        assert_eq!(*field, 43);
+        assert_eq!(calls.get(), 1, "closure must run exactly once");
+    }
+
+    #[test]
+    fn init_auto_skips_lazy_fields() {
+        let calls = Rc::new(Cell::new(0));
+        let calls_in_closure = Rc::clone(&calls);
+        let mut field = OnReady::lazy(move || {
+            calls_in_closure.set(calls_in_closure.get() + 1);
+            7
+        });
+
+        field.init_auto();
+        assert_eq!(
+            calls.get(),
+            0,
+            "init_auto() must not trigger lazy initializers"
+        );
+        assert!(!field.is_init());
+
+        assert_eq!(*field, 7);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn get_get_mut_is_init_across_states() {
+        let mut manual: OnReady<i32> = OnReady::manual();
+        assert_eq!(manual.get(), None);
+        assert_eq!(manual.get_mut(), None);
+        assert!(!manual.is_init());
+        manual.init(10);
+        assert_eq!(manual.get(), Some(&10));
+        assert_eq!(manual.get_mut(), Some(&mut 10));
+        assert!(manual.is_init());
+
+        let auto: OnReady<i32> = OnReady::new(|| 5);
+        assert_eq!(auto.get(), None);
+        assert!(!auto.is_init());
+
+        let lazy: OnReady<i32> = OnReady::lazy(|| 5);
+        assert_eq!(
+            lazy.get(),
+            None,
+            "get() must not trigger the lazy initializer"
+        );
+        assert!(!lazy.is_init());
+
+        let mut poisoned: OnReady<i32> = OnReady::new(|| panic!("boom"));
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| poisoned.init_auto()));
+        assert_eq!(poisoned.get(), None);
+        assert!(!poisoned.is_init());
+    }
+
+    #[test]
+    fn set_across_states() {
+        let mut manual: OnReady<i32> = OnReady::manual();
+        assert_eq!(manual.set(1), Ok(()));
+        assert_eq!(
+            manual.set(2),
+            Err(2),
+            "already initialized, must hand value back"
+        );
+
+        let mut auto: OnReady<i32> = OnReady::new(|| 1);
+        assert_eq!(auto.set(2), Err(2));
+
+        let mut lazy: OnReady<i32> = OnReady::lazy(|| 1);
+        assert_eq!(lazy.set(2), Err(2));
+
+        let mut poisoned: OnReady<i32> = OnReady::new(|| panic!("boom"));
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| poisoned.init_auto()));
+        assert_eq!(poisoned.set(9), Err(9));
+    }
+
+    #[test]
+    fn into_inner_and_take_across_states() {
+        let mut manual: OnReady<i32> = OnReady::manual();
+        assert_eq!(manual.take(), None);
+        manual.init(3);
+        assert_eq!(manual.take(), Some(3));
+        assert!(!manual.is_init());
+        assert_eq!(manual.take(), None);
+
+        let auto: OnReady<i32> = OnReady::new(|| 4);
+        assert_eq!(auto.into_inner(), None);
+
+        let lazy: OnReady<i32> = OnReady::lazy(|| 4);
+        assert_eq!(
+            lazy.into_inner(),
+            None,
+            "into_inner() must not trigger the lazy initializer"
+        );
+
+        let mut initialized: OnReady<i32> = OnReady::manual();
+        initialized.init(8);
+        assert_eq!(initialized.into_inner(), Some(8));
+
+        let mut poisoned: OnReady<i32> = OnReady::new(|| panic!("boom"));
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| poisoned.init_auto()));
+        assert_eq!(poisoned.take(), None);
+        assert_eq!(poisoned.into_inner(), None);
+    }
+}